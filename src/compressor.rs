@@ -0,0 +1 @@
+pub mod base2p15;
@@ -0,0 +1,13 @@
+//! `no_std + alloc` query core shared by the native indexer and the WASM query bundle (see
+//! [`crate::wasm`]).
+//!
+//! Everything needed to go from a [`SpectralBloomFilter`](crate::estimator::spectral_bloom_filter::SpectralBloomFilter)'s
+//! base2p15-encoded payload back to a frequency estimate lives here: base2p15 decoding, the
+//! hash functions, and the double-hashing index derivation. Previously this logic was
+//! re-implemented by hand in `src/assets/static_website_search.js`, which could silently drift
+//! from the Rust side. Compiling this module to WASM instead means the browser runs the exact
+//! same code the indexer used to build the filter.
+pub mod base2p15;
+pub mod fuse_filter;
+pub mod hasher;
+pub mod query;
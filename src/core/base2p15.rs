@@ -0,0 +1,62 @@
+use alloc::string::String;
+
+/// Encode a bit string ("0"/"1" characters) into base2p15: each run of 15 bits becomes one
+/// UTF-16 code unit offset into a printable range, so the payload can be embedded directly as a
+/// JS/JSON string literal.
+pub fn encode(bit_string: &str) -> String {
+    let n_padded_bits = (15 - bit_string.len() % 15) % 15;
+    let offset = 161;
+
+    let mut bit_string = String::from(bit_string);
+    for _ in 0..n_padded_bits {
+        bit_string.push('0');
+    }
+
+    let mut encoded: alloc::vec::Vec<u16> = bit_string
+        .as_bytes()
+        .chunks_exact(15)
+        .map(|fifteen_bits| {
+            fifteen_bits
+                .iter()
+                .map(|x| *x as u16 - 48)
+                .fold(0, |x, y| (x << 1) | y)
+                + offset
+        })
+        .collect();
+
+    let padding_char: u16 = core::char::from_digit(n_padded_bits as u32, 16).unwrap() as u16;
+    encoded.insert(0, padding_char);
+    char::decode_utf16(encoded.into_iter())
+        .map(|result| result.unwrap())
+        .collect()
+}
+
+/// Inverse of [`encode`].
+pub fn decode(base2p15_encoded: &str) -> String {
+    let offset = 0xa1;
+    let padding_char: char = base2p15_encoded.chars().next().unwrap();
+    let n_padded_bits = padding_char.to_digit(16).unwrap();
+    let mut decoded: String = base2p15_encoded
+        .chars()
+        .skip(1)
+        .map(|c| alloc::format!("{:015b}", c as u32 - offset))
+        .collect();
+    (0..n_padded_bits).for_each(|_| {
+        decoded.pop();
+    });
+    decoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    proptest! {
+
+        #[test]
+        fn proptest_reversible(bit_string in "[0-1]*") {
+            let s = decode(&encode(&bit_string));
+            prop_assert_eq!(s,bit_string);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::core::hasher::Hasher;
+
+/// The slot/fingerprint arithmetic shared by [`BinaryFuseFilter`](crate::estimator::binary_fuse_filter::BinaryFuseFilter)
+/// (which builds the fingerprint array) and [`VocabularyFilter`](crate::core::query::VocabularyFilter)
+/// (which only reads it back), so the two can't silently drift apart.
+///
+/// Maps `term` to the 3 slots (one per segment of length `segment_length`) its fingerprint is
+/// XORed into/read from.
+pub fn slots(hasher: &impl Hasher, term: &str, seed: u32, segment_length: u32) -> [usize; 3] {
+    let bytes = term.as_bytes();
+    [0u32, 1, 2].map(|segment| {
+        let within_segment = hasher.hash(bytes, seed.wrapping_add(segment)) % segment_length;
+        (segment * segment_length + within_segment) as usize
+    })
+}
+
+/// The 8-bit fingerprint stored/checked at `term`'s 3 slots.
+pub fn fingerprint(hasher: &impl Hasher, term: &str, seed: u32) -> u8 {
+    hasher.hash(term.as_bytes(), seed.wrapping_add(3)) as u8
+}
@@ -0,0 +1,92 @@
+use alloc::boxed::Box;
+
+pub mod fast;
+pub mod murmur3;
+
+/// A seeded 32-bit hash function usable by [`SpectralBloomFilter`](crate::estimator::spectral_bloom_filter::SpectralBloomFilter)
+/// and by [`query::get_frequency`](crate::core::query::get_frequency) to derive probe indices.
+///
+/// The false-positive-rate guarantees of the filter only hold if the hash spreads its output
+/// uniformly across seeds; see the `hash_quality` tests alongside each implementation.
+pub trait Hasher {
+    fn hash(&self, bytes: &[u8], seed: u32) -> u32;
+}
+
+impl Hasher for Box<dyn Hasher> {
+    fn hash(&self, bytes: &[u8], seed: u32) -> u32 {
+        (**self).hash(bytes, seed)
+    }
+}
+
+/// Shared hash-quality test bodies, modeled on ahash's avalanche/bucket-uniformity test suite.
+/// Both [`murmur3`] and [`fast`] call these from their own `#[test]` fns instead of
+/// hand-duplicating the assertions, so the two can't silently diverge.
+#[cfg(test)]
+pub(crate) mod quality_test {
+    use super::Hasher;
+
+    /// For every single-bit flip of a sample corpus, each output bit should flip with
+    /// probability near 0.5.
+    pub(crate) fn assert_avalanche(hasher: &impl Hasher) {
+        let corpus: alloc::vec::Vec<alloc::vec::Vec<u8>> = (0..64u32)
+            .map(|i| alloc::format!("token-{}", i).into_bytes())
+            .collect();
+        let mut bit_flip_counts = [0u32; 32];
+        let mut trials = 0u32;
+
+        for bytes in &corpus {
+            let base = hasher.hash(bytes, 0);
+            for bit in 0..bytes.len() * 8 {
+                let mut flipped = bytes.clone();
+                flipped[bit / 8] ^= 1 << (bit % 8);
+                let changed = hasher.hash(&flipped, 0) ^ base;
+                for out_bit in 0..32 {
+                    if (changed >> out_bit) & 1 == 1 {
+                        bit_flip_counts[out_bit] += 1;
+                    }
+                }
+                trials += 1;
+            }
+        }
+
+        for (out_bit, &count) in bit_flip_counts.iter().enumerate() {
+            let ratio = count as f64 / trials as f64;
+            assert!(
+                (ratio - 0.5).abs() < 0.1,
+                "output bit {} flipped with ratio {}, expected close to 0.5",
+                out_bit,
+                ratio
+            );
+        }
+    }
+
+    /// Hash a large token set modulo a bucket count and check the distribution isn't
+    /// significantly non-uniform (chi-squared goodness-of-fit). With 255 degrees of freedom, the
+    /// 99.9% critical value is ~330; a well-distributed hash should land well under that.
+    pub(crate) fn assert_chi_squared_uniformity(hasher: &impl Hasher) {
+        let n_buckets = 256usize;
+        let n_tokens = 20_000u32;
+        let mut buckets = [0u32; 256];
+
+        for i in 0..n_tokens {
+            let token = alloc::format!("word-{}", i);
+            let h = hasher.hash(token.as_bytes(), 0);
+            buckets[(h as usize) % n_buckets] += 1;
+        }
+
+        let expected = n_tokens as f64 / n_buckets as f64;
+        let chi_squared: f64 = buckets
+            .iter()
+            .map(|&observed| {
+                let diff = observed as f64 - expected;
+                diff * diff / expected
+            })
+            .sum();
+
+        assert!(
+            chi_squared < 330.0,
+            "chi-squared statistic {} suggests a non-uniform hash",
+            chi_squared
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::core::hasher::Hasher;
+
+/// A fast, non-cryptographic multiply-xor-fold hasher in the spirit of FxHash/ahash's fallback
+/// path: read the input in 8-byte words, mix each one in with a rotate-xor-multiply step, then
+/// fold the 64-bit state down to 32 bits. Cheaper per call than [`murmurhash3_x86_32`](crate::core::hasher::murmur3::murmurhash3_x86_32),
+/// at the cost of weaker diffusion guarantees.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct FastHasher;
+
+// Arbitrary odd 64-bit constant used as both the multiplier and the seed mixer.
+const MULTIPLIER: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl Hasher for FastHasher {
+    fn hash(&self, bytes: &[u8], seed: u32) -> u32 {
+        let mut state = (seed as u64) ^ MULTIPLIER;
+        for chunk in bytes.chunks(8) {
+            let mut word_bytes = [0u8; 8];
+            word_bytes[..chunk.len()].copy_from_slice(chunk);
+            let word = u64::from_le_bytes(word_bytes);
+            state = (state.rotate_left(5) ^ word).wrapping_mul(MULTIPLIER);
+        }
+        state = state ^ (bytes.len() as u64);
+        // Xor-fold the 64-bit state down to 32 bits.
+        let folded = ((state >> 32) ^ state) as u32;
+        // Finalize with murmur3-style xor-shift/multiply rounds (fmix32): the rotate-xor-multiply
+        // loop above barely diffuses into the low output bits (multiplication only carries
+        // upward), so without this the hash fails avalanche/bucket-uniformity checks.
+        fmix32(folded)
+    }
+}
+
+/// murmur3's `fmix32` finalizer: scrambles every output bit so it depends on the whole input.
+fn fmix32(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^= h >> 16;
+    h
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_input_same_seed_is_deterministic() {
+        let hasher = FastHasher;
+        assert_eq!(hasher.hash(b"hello", 0), hasher.hash(b"hello", 0));
+    }
+
+    #[test]
+    fn different_seeds_differ() {
+        let hasher = FastHasher;
+        assert_ne!(hasher.hash(b"hello", 0), hasher.hash(b"hello", 1));
+    }
+
+    // Hash quality tests, modeled on ahash's avalanche/bucket-uniformity test suite. Shared with
+    // `crate::core::hasher::murmur3::test` via `quality_test` so the two can't silently diverge.
+
+    #[test]
+    fn avalanche() {
+        crate::core::hasher::quality_test::assert_avalanche(&FastHasher);
+    }
+
+    #[test]
+    fn chi_squared_uniformity() {
+        crate::core::hasher::quality_test::assert_chi_squared_uniformity(&FastHasher);
+    }
+}
@@ -1,3 +1,16 @@
+use crate::core::hasher::Hasher;
+
+/// [`Hasher`] implementation backed by [`murmurhash3_x86_32`], the default used by
+/// [`SpectralBloomFilter`](crate::estimator::spectral_bloom_filter::SpectralBloomFilter).
+#[derive(Default, Debug, Clone, Copy)]
+pub struct MurmurHasher;
+
+impl Hasher for MurmurHasher {
+    fn hash(&self, bytes: &[u8], seed: u32) -> u32 {
+        murmurhash3_x86_32(bytes, seed)
+    }
+}
+
 pub fn murmurhash3_x86_32(bytes: &[u8], seed: u32) -> u32 {
     // https://en.wikipedia.org/wiki/MurmurHash
     let c1 = 0xcc9e2d51;
@@ -41,7 +54,7 @@ pub fn murmurhash3_x86_32(bytes: &[u8], seed: u32) -> u32 {
 }
 #[cfg(test)]
 mod test {
-    use super::murmurhash3_x86_32;
+    use super::{murmurhash3_x86_32, MurmurHasher};
 
     #[test]
     fn test_empty_string() {
@@ -61,4 +74,19 @@ mod test {
         assert!(murmurhash3_x86_32("Lorem ipsum dolor sit amet, consectetur adipiscing elit. Etiam at consequat massa. Cras eleifend pellentesque ex, at dignissim libero maximus ut. Sed eget nulla felis".as_bytes(), 0)
             == 1004899618);
     }
+
+    // Hash quality tests, modeled on ahash's avalanche/bucket-uniformity test suite. These
+    // matter because SpectralBloomFilter's false-positive-rate formula assumes the hash spreads
+    // indices uniformly; the old additive `naive_hash` failed this badly. Shared with
+    // `crate::core::hasher::fast::test` via `quality_test` so the two can't silently diverge.
+
+    #[test]
+    fn avalanche() {
+        crate::core::hasher::quality_test::assert_avalanche(&MurmurHasher);
+    }
+
+    #[test]
+    fn chi_squared_uniformity() {
+        crate::core::hasher::quality_test::assert_chi_squared_uniformity(&MurmurHasher);
+    }
 }
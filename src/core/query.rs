@@ -0,0 +1,184 @@
+use crate::core::base2p15;
+use crate::core::fuse_filter::{fingerprint, slots};
+use crate::core::hasher::Hasher;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::Deserialize;
+
+/// One document's entry in the search index, as emitted by `main.rs` and consumed by both the
+/// native CLI and [`crate::wasm::SearchIndex`].
+#[derive(Debug, Deserialize)]
+pub struct IndexEntry {
+    pub url: String,
+    pub title: String,
+    pub sbf_base2p15: String,
+    pub width: u32,
+    pub size: u32,
+    pub n_hash_functions: u32,
+    /// Total number of (indexed) term occurrences in the document, used as `docLen` in BM25.
+    pub doc_len: u32,
+}
+
+/// BM25 ranking parameters and precomputed per-term IDF, as emitted by `main.rs` alongside the
+/// index and mirrored by `static_website_search.js`'s `IDF`/`AVG_DOC_LEN`/`BM25_K1`/`BM25_B`.
+#[derive(Debug)]
+pub struct Bm25Params<'a> {
+    pub idf: &'a BTreeMap<String, f32>,
+    pub avg_doc_len: f32,
+    pub k1: f32,
+    pub b: f32,
+}
+
+/// A read-only view of a [`BinaryFuseFilter`](crate::estimator::binary_fuse_filter::BinaryFuseFilter)
+/// over the document vocabulary, used as a cheap prefilter so a query term absent from every
+/// document can be rejected in one lookup, before consulting any document's `IndexEntry`.
+#[derive(Debug)]
+pub struct VocabularyFilter<'a> {
+    pub fingerprints: &'a [u8],
+    pub segment_length: u32,
+    pub seed: u32,
+}
+
+impl<'a> VocabularyFilter<'a> {
+    /// Test whether `term` might be in the vocabulary the filter was built from. A `false` result
+    /// is certain; a `true` result has a small (roughly 1/256) chance of being a false positive.
+    pub fn contains(&self, term: &str, hasher: &impl Hasher) -> bool {
+        let term_slots = slots(hasher, term, self.seed, self.segment_length);
+        let term_fingerprint = fingerprint(hasher, term, self.seed);
+        let xor: u8 = term_slots.iter().map(|&s| self.fingerprints[s]).fold(0, |a, b| a ^ b);
+        term_fingerprint == xor
+    }
+}
+
+/// Given a token, return n indices that correspond to a location in the (decoded) SBF bit
+/// string, where n = `n_hash_functions`.
+///
+/// This is the same Kirsch-Mitzenmacher double-hashing recurrence used at index build time by
+/// `SpectralBloomFilter::hash_indices`: two base hashes `h1`/`h2` are computed once and the i-th
+/// index is derived as `h1 + i*h2 (mod sbf_size)`, with `h2` forced odd.
+pub fn hash_indices(hasher: &impl Hasher, token: &str, n_hash_functions: u32, sbf_size: u32) -> Vec<usize> {
+    let h1 = hasher.hash(token.as_bytes(), 0);
+    let h2 = hasher.hash(token.as_bytes(), 1) | 1;
+    (0..n_hash_functions)
+        .map(|i| (h1.wrapping_add(i.wrapping_mul(h2)) % sbf_size) as usize)
+        .collect()
+}
+
+/// Decode `entry.sbf_base2p15` and estimate the frequency of `token` within it.
+pub fn get_frequency(entry: &IndexEntry, token: &str, hasher: &impl Hasher) -> u32 {
+    let bits = base2p15::decode(&entry.sbf_base2p15);
+    let indices = hash_indices(hasher, token, entry.n_hash_functions, entry.size);
+    indices
+        .into_iter()
+        .map(|i| {
+            let start = i * entry.width as usize;
+            let slot = &bits[start..start + entry.width as usize];
+            u32::from_str_radix(slot, 2).unwrap_or(0)
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Which terms a query decomposes into, mirroring `preprocessor::ngram::Mode`. Kept as its own
+/// copy (rather than depending on the std-heavy `preprocessor` module) so this module stays
+/// `no_std + alloc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NgramMode {
+    Word,
+    Ngram,
+    Combined,
+}
+
+/// Overlapping character k-grams of `word`. Mirrors `preprocessor::ngram::char_ngrams`.
+pub fn char_ngrams(word: &str, k: usize) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < k {
+        return Vec::new();
+    }
+    (0..=chars.len() - k)
+        .map(|i| chars[i..i + k].iter().collect())
+        .collect()
+}
+
+/// Lowercase, strip non-alphabetic characters and split on whitespace, mirroring the tokenizer
+/// used at index time (`preprocessor::naive::tokenize`), minus the stopword pass: a query term
+/// that happens to be a stopword should still be searchable verbatim. If `mode` calls for
+/// k-grams, the words are further decomposed the same way `preprocessor::ngram::fold_ngrams`
+/// decomposed the index, so query tokens land on the terms the filter actually contains.
+pub fn tokenize_query(term: &str, ngram_size: usize, mode: NgramMode) -> Vec<String> {
+    let words: Vec<String> = term
+        .chars()
+        .map(|c| if c.is_alphabetic() { c.to_ascii_lowercase() } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .map(ToString::to_string)
+        .collect();
+
+    match mode {
+        NgramMode::Word => words,
+        NgramMode::Ngram => words
+            .iter()
+            .flat_map(|word| char_ngrams(word, ngram_size))
+            .collect(),
+        NgramMode::Combined => {
+            let mut tokens = words.clone();
+            tokens.extend(words.iter().flat_map(|word| char_ngrams(word, ngram_size)));
+            tokens
+        }
+    }
+}
+
+/// BM25 score contribution of a single query term against one document. Mirrors
+/// `bm25TermScore` in `static_website_search.js`.
+fn bm25_term_score(entry: &IndexEntry, tf: u32, idf: f32, bm25: &Bm25Params) -> f32 {
+    if tf == 0 {
+        return 0.0;
+    }
+    let tf = tf as f32;
+    let numerator = tf * (bm25.k1 + 1.0);
+    let denominator =
+        tf + bm25.k1 * (1.0 - bm25.b + bm25.b * entry.doc_len as f32 / bm25.avg_doc_len);
+    idf * (numerator / denominator)
+}
+
+/// Score every entry against a query term with BM25, highest score first.
+///
+/// Query tokens absent from `vocab_filter` are dropped before scoring, the same one-lookup
+/// rejection `static_website_search.js`'s `mightBeInVocabulary` performs, instead of probing
+/// every document's `SpectralBloomFilter` for a term no document contains.
+pub fn search(
+    index: &[IndexEntry],
+    term: &str,
+    ngram_size: usize,
+    mode: NgramMode,
+    hasher: &impl Hasher,
+    bm25: &Bm25Params,
+    vocab_filter: &VocabularyFilter,
+) -> Vec<(String, String, f32)> {
+    let query_tokens: Vec<String> = tokenize_query(term, ngram_size, mode)
+        .into_iter()
+        .filter(|token| vocab_filter.contains(token, hasher))
+        .collect();
+    if query_tokens.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results: Vec<(String, String, f32)> = index
+        .iter()
+        .map(|entry| {
+            let score = query_tokens
+                .iter()
+                .filter_map(|token| {
+                    let idf = *bm25.idf.get(token)?;
+                    let tf = get_frequency(entry, token, hasher);
+                    Some(bm25_term_score(entry, tf, idf, bm25))
+                })
+                .sum();
+            (entry.url.clone(), entry.title.clone(), score)
+        })
+        .filter(|(_, _, score)| *score > 0.0)
+        .collect();
+    results.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(::core::cmp::Ordering::Equal));
+    results
+}
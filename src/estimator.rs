@@ -0,0 +1,3 @@
+pub mod binary_fuse_filter;
+pub mod bitpacker;
+pub mod spectral_bloom_filter;
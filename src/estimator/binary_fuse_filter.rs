@@ -0,0 +1,169 @@
+use crate::core::fuse_filter::{fingerprint, slots};
+use crate::hasher::murmur3::MurmurHasher;
+use crate::hasher::Hasher;
+use std::fmt;
+
+/// Maximum number of times construction retries with a new seed before giving up. Peeling a
+/// 3-wise fuse graph sized at the standard 1.23x overhead succeeds with overwhelming probability
+/// on the first or second attempt; this is just a backstop against a pathological vocabulary.
+const MAX_BUILD_ATTEMPTS: u32 = 100;
+
+/**
+A compact membership filter over a vocabulary of terms (a "binary fuse" / 3-wise XOR filter),
+used as a cheap global prefilter so a query term absent from every document can be rejected in
+one lookup, at ~1 byte/key, instead of probing every document's [`SpectralBloomFilter`](crate::estimator::spectral_bloom_filter::SpectralBloomFilter).
+
+# Construction
+
+The fingerprint array is sized to `~1.23 * n` slots, split into 3 equal segments. Each key is
+mapped via three hashes into one slot per segment. The array is filled by *peeling*: repeatedly
+find a slot touched by exactly one remaining key, record `(key, slot)`, and remove that key from
+the graph; if every key peels, the fingerprints are assigned in reverse peel order so that
+`arr[slot] = fingerprint(key) ^ arr[other_slot_1] ^ arr[other_slot_2]`. A key that can't be
+peeled (the graph has a 2-core) means construction retries with a new seed.
+
+# Guarantees
+
+* No false negatives: every key the filter was built from reports present.
+* A false positive rate of roughly `1/256` (the fingerprint width) for keys outside the vocabulary.
+*/
+#[derive(fmt::Debug)]
+pub struct BinaryFuseFilter<H: Hasher = MurmurHasher> {
+    fingerprints: Vec<u8>,
+    segment_length: u32,
+    seed: u32,
+    hasher: H,
+}
+
+impl<H: Hasher> BinaryFuseFilter<H> {
+    /// Build a filter over `vocabulary`, retrying with a new seed if a given attempt's fuse
+    /// graph fails to fully peel.
+    pub fn build(vocabulary: &[String], hasher: H) -> Self {
+        let n = vocabulary.len() as u32;
+        let segment_length = std::cmp::max(1, ((n as f32 * 1.23).ceil() as u32 + 2) / 3);
+
+        for attempt in 0..MAX_BUILD_ATTEMPTS {
+            if let Some(fingerprints) = Self::try_build(vocabulary, segment_length, attempt, &hasher) {
+                return BinaryFuseFilter {
+                    fingerprints,
+                    segment_length,
+                    seed: attempt,
+                    hasher,
+                };
+            }
+        }
+        panic!("binary fuse filter: fuse graph failed to peel after {} attempts", MAX_BUILD_ATTEMPTS);
+    }
+
+    fn try_build(vocabulary: &[String], segment_length: u32, seed: u32, hasher: &H) -> Option<Vec<u8>> {
+        let n = vocabulary.len();
+        let array_len = (3 * segment_length) as usize;
+
+        let key_slots: Vec<[usize; 3]> = vocabulary
+            .iter()
+            .map(|term| slots(hasher, term, seed, segment_length))
+            .collect();
+
+        let mut slot_keys: Vec<Vec<usize>> = vec![Vec::new(); array_len];
+        let mut degree: Vec<usize> = vec![0; array_len];
+        for (key, slots) in key_slots.iter().enumerate() {
+            for &slot in slots {
+                slot_keys[slot].push(key);
+                degree[slot] += 1;
+            }
+        }
+
+        let mut queue: Vec<usize> = (0..array_len).filter(|&slot| degree[slot] == 1).collect();
+        let mut removed = vec![false; n];
+        let mut peel_order: Vec<(usize, usize)> = Vec::with_capacity(n);
+
+        while let Some(slot) = queue.pop() {
+            if degree[slot] != 1 {
+                continue;
+            }
+            let key = match slot_keys[slot].iter().find(|&&k| !removed[k]) {
+                Some(&k) => k,
+                None => continue,
+            };
+            removed[key] = true;
+            peel_order.push((key, slot));
+
+            for &s in &key_slots[key] {
+                degree[s] -= 1;
+                if degree[s] == 1 {
+                    queue.push(s);
+                }
+            }
+        }
+
+        if peel_order.len() != n {
+            return None;
+        }
+
+        let mut fingerprints = vec![0u8; array_len];
+        for &(key, slot) in peel_order.iter().rev() {
+            let other_xor: u8 = key_slots[key]
+                .iter()
+                .filter(|&&s| s != slot)
+                .map(|&s| fingerprints[s])
+                .fold(0, |a, b| a ^ b);
+            fingerprints[slot] = fingerprint(hasher, &vocabulary[key], seed) ^ other_xor;
+        }
+
+        Some(fingerprints)
+    }
+
+    /// Test whether `term` might be in the vocabulary the filter was built from. A `false` result
+    /// is certain; a `true` result has a small (roughly 1/256) chance of being a false positive.
+    pub fn contains(&self, term: &str) -> bool {
+        let term_slots = slots(&self.hasher, term, self.seed, self.segment_length);
+        let term_fingerprint = fingerprint(&self.hasher, term, self.seed);
+        let xor: u8 = term_slots.iter().map(|&s| self.fingerprints[s]).fold(0, |a, b| a ^ b);
+        term_fingerprint == xor
+    }
+
+    /// The packed fingerprint array, in slot order, for serializing into the search index.
+    pub fn fingerprints(&self) -> &[u8] {
+        &self.fingerprints
+    }
+
+    pub fn segment_length(&self) -> u32 {
+        self.segment_length
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocabulary() -> Vec<String> {
+        ["apple", "banana", "cherry", "date", "elderberry", "fig", "grape", "honeydew"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn contains_every_built_term() {
+        let vocabulary = vocabulary();
+        let filter = BinaryFuseFilter::build(&vocabulary, MurmurHasher);
+        for term in &vocabulary {
+            assert!(filter.contains(term));
+        }
+    }
+
+    #[test]
+    fn rejects_most_absent_terms() {
+        let vocabulary = vocabulary();
+        let filter = BinaryFuseFilter::build(&vocabulary, MurmurHasher);
+        let absent: Vec<String> = (0..1000).map(|i| format!("not-a-fruit-{}", i)).collect();
+        let false_positives = absent.iter().filter(|term| filter.contains(term)).count();
+        // 8-bit fingerprints give a false positive rate around 1/256; 1000 trials should stay
+        // well clear of, say, a 10% rate.
+        assert!(false_positives < 100, "false positive rate too high: {}/1000", false_positives);
+    }
+}
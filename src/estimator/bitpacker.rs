@@ -0,0 +1,110 @@
+use std::fmt;
+
+/// A contiguous array of fixed-width, sub-byte-aligned counters, in the spirit of tantivy's
+/// `BitPacker`: slot `i` occupies bits `[i*width, (i+1)*width)` of the backing byte buffer, so a
+/// `width` of 4 packs 8x as densely as a `Vec<u32>` of the same length.
+#[derive(Clone, fmt::Debug)]
+pub struct BitPacker {
+    bytes: Vec<u8>,
+    len: usize,
+    width: u32,
+}
+
+impl BitPacker {
+    /// Allocate a packed array of `len` zeroed slots, each `width` bits wide.
+    pub fn new(len: usize, width: u32) -> Self {
+        let total_bits = len as u64 * width as u64;
+        let n_bytes = ((total_bits + 7) / 8) as usize;
+        BitPacker {
+            bytes: vec![0; n_bytes],
+            len,
+            width,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Read slot `i`: load the (up to 8) bytes straddling its bit range into a `u64` mini-buffer,
+    /// then shift and mask out the `width` bits that belong to it.
+    pub fn get(&self, i: usize) -> u32 {
+        let bit_offset = i as u64 * self.width as u64;
+        let byte_offset = (bit_offset / 8) as usize;
+        let bit_shift = bit_offset % 8;
+
+        let mut buffer: u64 = 0;
+        for (j, &byte) in self.bytes[byte_offset..].iter().take(8).enumerate() {
+            buffer |= (byte as u64) << (8 * j);
+        }
+
+        let mask = (1u64 << self.width) - 1;
+        ((buffer >> bit_shift) & mask) as u32
+    }
+
+    /// Write slot `i`, read-modify-writing only the bytes its bit range straddles.
+    pub fn set(&mut self, i: usize, value: u32) {
+        let bit_offset = i as u64 * self.width as u64;
+        let byte_offset = (bit_offset / 8) as usize;
+        let bit_shift = bit_offset % 8;
+        let n = (self.bytes.len() - byte_offset).min(8);
+
+        let mut buffer: u64 = 0;
+        for j in 0..n {
+            buffer |= (self.bytes[byte_offset + j] as u64) << (8 * j);
+        }
+
+        let mask = ((1u64 << self.width) - 1) << bit_shift;
+        buffer = (buffer & !mask) | ((value as u64) << bit_shift);
+
+        for j in 0..n {
+            self.bytes[byte_offset + j] = (buffer >> (8 * j)) as u8;
+        }
+    }
+
+    /// Render every slot as a `width`-bit zero-padded binary string, in slot order. This is the
+    /// same bit string the unpacked `Vec<u32>` representation used to produce, so `base2p15`
+    /// doesn't need to know the storage changed.
+    pub fn as_bit_string(&self) -> String {
+        (0..self.len)
+            .map(|i| format!("{:0width$b}", self.get(i), width = self.width as usize))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn hand_written() {
+        let mut packer = BitPacker::new(5, 4);
+        packer.set(0, 1);
+        packer.set(1, 15);
+        packer.set(2, 0);
+        packer.set(3, 9);
+        packer.set(4, 2);
+        assert_eq!(packer.get(0), 1);
+        assert_eq!(packer.get(1), 15);
+        assert_eq!(packer.get(2), 0);
+        assert_eq!(packer.get(3), 9);
+        assert_eq!(packer.get(4), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_roundtrip(values in prop::collection::vec(0u32..16, 1..200)) {
+            let mut packer = BitPacker::new(values.len(), 4);
+            for (i, &v) in values.iter().enumerate() {
+                packer.set(i, v);
+            }
+            let roundtripped: Vec<u32> = (0..values.len()).map(|i| packer.get(i)).collect();
+            prop_assert_eq!(roundtripped, values);
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::hasher::murmur3::murmurhash3_x86_32 as hash_fn;
+use crate::estimator::bitpacker::BitPacker;
+use crate::hasher::murmur3::MurmurHasher;
+use crate::hasher::Hasher;
+use rand::Rng;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -13,32 +16,34 @@ Spectral Bloom Filter is a probabilistic data structure used to estimate frequen
 
 # Members
 * n_hash_functions: The number of hash functions utilized by the filter
-* sbf: The count vector
+* sbf: The count vector, packed into exactly `width` bits per counter (see [`bitpacker::BitPacker`](crate::estimator::bitpacker::BitPacker))
 
 # Example
 
 ```
 use static_website_search::estimator::spectral_bloom_filter::SpectralBloomFilter;
+use static_website_search::hasher::murmur3::MurmurHasher;
 use std::collections::HashMap;
 
 let mut hash_map: HashMap<String, u32> = HashMap::new();
 hash_map.insert("a".to_string(), 5);
 
 // Create a SBF with false_positive_rate = 1% and width = 4 (max frequency = 2^4 - 1 = 15)
-let sbf = SpectralBloomFilter::new(&hash_map, 0.01, 4);
+let sbf = SpectralBloomFilter::new(&hash_map, 0.01, 4, MurmurHasher);
 
 assert_eq!(sbf.get_frequency(&"a".to_string()), 5);
 assert_eq!(sbf.get_frequency(&"x".to_string()), 0);
 ```
 */
 #[derive(fmt::Debug)]
-pub struct SpectralBloomFilter {
+pub struct SpectralBloomFilter<H: Hasher = MurmurHasher> {
     pub n_hash_functions: u32,
-    pub sbf: Vec<u32>,
+    pub sbf: BitPacker,
     pub width: u32,
+    hasher: H,
 }
 
-impl SpectralBloomFilter {
+impl<H: Hasher> SpectralBloomFilter<H> {
     /**
     Create new Spectral Bloom Filter (SBF)
 
@@ -46,21 +51,27 @@ impl SpectralBloomFilter {
     * counter: Multiset represented as HashMap with elements as key, frequencies as value
     * false_positive_rate: A configurable false positive rate in range \[0,1\]. Recommended value 0.1
     * width: Number of bits to represent frequency in SBF. Overshooting counter frequencies will be automatically converted to 2^width-1
+    * hasher: The [`Hasher`] used to derive probe indices, e.g. [`MurmurHasher`] or [`FastHasher`](crate::hasher::fast::FastHasher)
     */
-    pub fn new(counter: &HashMap<String, u32>, false_positive_rate: f32, width: u32) -> Self {
+    pub fn new(
+        counter: &HashMap<String, u32>,
+        false_positive_rate: f32,
+        width: u32,
+        hasher: H,
+    ) -> Self {
         // Compute optimal size
         let (sbf_size, n_hash_functions) =
             Self::optimal_size(counter.keys().count() as u32, false_positive_rate);
 
-        // Create SBF of sbf_size
-        let mut sbf: Vec<u32> = vec![0; sbf_size as usize];
+        // Create SBF of sbf_size, with each counter packed into exactly `width` bits
+        let mut sbf = BitPacker::new(sbf_size as usize, width);
 
         // Define function to insert item in SBF
-        let insert_item = |(key, &frequency)| {
-            let indices = Self::hash_indices(key, n_hash_functions, sbf_size);
+        let insert_item = |(key, &frequency): (&String, &u32)| {
+            let indices = Self::hash_indices(&hasher, key, n_hash_functions, sbf_size);
             let upper_bound = 2u32.pow(width) - 1;
 
-            let minimum_value = indices.iter().map(|&i| sbf[i]).min().unwrap();
+            let minimum_value = indices.iter().map(|&i| sbf.get(i)).min().unwrap();
 
             // In case of overflow, set to MAX value
             let minimum_value = match minimum_value.checked_add(frequency) {
@@ -68,8 +79,8 @@ impl SpectralBloomFilter {
                 None => 2u32.pow(width) - 1,
             };
             indices.iter().for_each(|&i| {
-                if sbf[i] <= minimum_value {
-                    sbf[i] = minimum_value;
+                if sbf.get(i) <= minimum_value {
+                    sbf.set(i, minimum_value);
                 }
             });
         };
@@ -82,20 +93,33 @@ impl SpectralBloomFilter {
             n_hash_functions: n_hash_functions,
             sbf: sbf,
             width: width,
+            hasher: hasher,
         }
     }
 
     /**
     Given a token, return n indices that correspond to a location in sbf, where n = n_hash_functions
 
+    Uses Kirsch-Mitzenmacher double hashing: instead of running the hash function once per
+    seed (`n_hash_functions` calls), two base hashes `h1`/`h2` are computed once and the i-th
+    index is derived as `h1 + i*h2 (mod sbf_size)`. `h2` is forced odd so the derived indices
+    don't collapse into shorter cycles when it shares a factor with `sbf_size`. This keeps the
+    hashing cost at O(1) `hasher.hash` calls per token regardless of `n_hash_functions`, while
+    preserving the uniform-distribution assumptions behind `optimal_size` and the no-false-negative
+    guarantee. The JS reference implementation in `src/assets/static_website_search.js` mirrors
+    this same recurrence so that index and query always agree.
+
     # Arguments
+    * hasher: The [`Hasher`] used to derive the two base hashes
     * key: An element of the multiset / counter
     * n_hash_functions: Then number of hash_functions
     * sbf_size: The size which will be used for modulo
     */
-    fn hash_indices(key: &String, n_hash_functions: u32, sbf_size: u32) -> Vec<usize> {
+    fn hash_indices(hasher: &H, key: &String, n_hash_functions: u32, sbf_size: u32) -> Vec<usize> {
+        let h1 = hasher.hash(key.as_bytes(), 0);
+        let h2 = hasher.hash(key.as_bytes(), 1) | 1;
         (0..n_hash_functions)
-            .map(|i| (hash_fn(key.as_bytes(), i) % sbf_size) as usize)
+            .map(|i| (h1.wrapping_add(i.wrapping_mul(h2)) % sbf_size) as usize)
             .collect()
     }
 
@@ -103,11 +127,16 @@ impl SpectralBloomFilter {
     Compute the optimal size using the formulae from
 
     https://stackoverflow.com/questions/658439/how-many-hash-functions-does-my-bloom-filter-need
+
+    `n_unique_tokens` is floored at 1 so a filter created for an empty (or not-yet-populated)
+    vocabulary still gets a usable, non-zero-sized table instead of the formula degenerating to
+    `sbf_size = 0` (which later panics on `% sbf_size`/`gen_range(0..sbf_size)`).
     */
     fn optimal_size(n_unique_tokens: u32, false_positive_rate: f32) -> (u32, u32) {
-        let sbf_size = -((n_unique_tokens as f32) * false_positive_rate.ln() / 2_f32.ln().powi(2));
-        let n_hash_functions = (sbf_size / n_unique_tokens as f32) * 2_f32.ln();
-        (sbf_size.ceil() as u32, n_hash_functions.ceil() as u32)
+        let n_unique_tokens = std::cmp::max(n_unique_tokens, 1) as f32;
+        let sbf_size = -(n_unique_tokens * false_positive_rate.ln() / 2_f32.ln().powi(2));
+        let n_hash_functions = (sbf_size / n_unique_tokens) * 2_f32.ln();
+        (std::cmp::max(sbf_size.ceil() as u32, 1), std::cmp::max(n_hash_functions.ceil() as u32, 1))
     }
 
     /**
@@ -125,15 +154,135 @@ impl SpectralBloomFilter {
     * key: An element of the multiset / counter
     */
     pub fn get_frequency(self: &Self, key: &String) -> u32 {
-        let indices = Self::hash_indices(key, self.n_hash_functions, self.sbf.len() as u32);
-        indices.into_iter().map(|i| self.sbf[i]).min().unwrap()
+        let indices = Self::hash_indices(&self.hasher, key, self.n_hash_functions, self.sbf.len() as u32);
+        indices.into_iter().map(|i| self.sbf.get(i)).min().unwrap()
     }
 
     pub fn as_bit_string(&self) -> String {
-        self.sbf
-            .iter()
-            .map(|&x| format!("{:0width$b}", x, width = self.width as usize))
-            .fold(String::new(), |x, y| format!("{}{}", x, y))
+        self.sbf.as_bit_string()
+    }
+}
+
+/**
+A streaming variant of [`SpectralBloomFilter`] for a long-lived index that absorbs new documents
+without rebuilding from a full `HashMap` and without unbounded memory growth.
+
+Each [`insert`](Self::insert) first decrements `p` cells, starting at a random offset and
+wrapping around the table, before writing the new token's frequency into its `n_hash_functions`
+hashed cells. Decrementing makes room for new entries at constant memory, at the cost of a small,
+bounded false-negative probability for stale tokens that haven't been reinserted recently.
+
+# Guarantees
+
+Unlike [`SpectralBloomFilter`], this mode **can** undershoot (and even forget) entries over time;
+`p` trades off how quickly.
+*/
+#[derive(fmt::Debug)]
+pub struct StableSpectralBloomFilter<H: Hasher = MurmurHasher> {
+    pub n_hash_functions: u32,
+    pub sbf: BitPacker,
+    pub width: u32,
+    pub p: u32,
+    hasher: H,
+}
+
+/**
+Recommended decrement count `P` for [`StableSpectralBloomFilter::new`], derived from
+`false_positive_rate` and `width` so the expected number of saturated (capped) cells stays
+bounded, rather than a hand-picked constant. Each insert writes `n_hash_functions` cells (purely
+a function of `false_positive_rate`, same as `SpectralBloomFilter::optimal_size`); a wider
+`width` gives each cell more headroom (`2^width - 1`) before it saturates. `P` scales up with
+`n_hash_functions` and down with that headroom, so eviction work roughly keeps pace with insert
+work regardless of how those two parameters are chosen.
+
+Callers may still pass a smaller/larger `P` to `new` directly to trade eviction speed against the
+false-negative rate explicitly.
+*/
+pub fn recommended_decrement(false_positive_rate: f32, width: u32) -> u32 {
+    // `n_unique_tokens` cancels out of `optimal_size`'s n_hash_functions formula, so any positive
+    // value gives the same result, and `optimal_size` doesn't depend on the hasher, so any
+    // concrete `H` gives the same result too; `MurmurHasher` just stands in for "some hasher".
+    let (_, n_hash_functions) = SpectralBloomFilter::<MurmurHasher>::optimal_size(1, false_positive_rate);
+    let max_count = 2u32.pow(width) - 1;
+    std::cmp::max(1, (n_hash_functions + max_count - 1) / max_count)
+}
+
+impl<H: Hasher> StableSpectralBloomFilter<H> {
+    /**
+    Create an empty stable SBF sized for `expected_tokens` unique tokens.
+
+    # Arguments
+    * expected_tokens: Expected vocabulary size, used to size the table via the same formula as [`SpectralBloomFilter`]
+    * false_positive_rate: See [`SpectralBloomFilter::new`]
+    * width: See [`SpectralBloomFilter::new`]
+    * p: Number of cells decremented per insert. Larger `p` evicts stale entries faster, at a higher false-negative rate. See [`recommended_decrement`] for a sensible default
+    * hasher: The [`Hasher`] used to derive probe indices
+    */
+    pub fn new(expected_tokens: u32, false_positive_rate: f32, width: u32, p: u32, hasher: H) -> Self {
+        let (sbf_size, n_hash_functions) =
+            SpectralBloomFilter::<H>::optimal_size(expected_tokens, false_positive_rate);
+        StableSpectralBloomFilter {
+            n_hash_functions,
+            sbf: BitPacker::new(sbf_size as usize, width),
+            width,
+            p,
+            hasher,
+        }
+    }
+
+    /**
+    Insert (or increment) a token's frequency, evicting room for it first.
+
+    # Arguments
+    * token: An element of the multiset
+    * frequency: The amount to add to the token's current estimate
+    */
+    pub fn insert(&mut self, token: &str, frequency: u32) {
+        let sbf_size = self.sbf.len() as u32;
+
+        // Evict: decrement p cells from a random offset (wrapping), saturating at 0, to bound
+        // the table's memory use for an index that never stops growing.
+        let start = rand::thread_rng().gen_range(0..sbf_size);
+        (0..self.p).for_each(|j| {
+            let i = ((start + j) % sbf_size) as usize;
+            self.sbf.set(i, self.sbf.get(i).saturating_sub(1));
+        });
+
+        // Then write the new frequency into the token's hashed cells, same as SpectralBloomFilter::new.
+        let key = token.to_string();
+        let indices =
+            SpectralBloomFilter::<H>::hash_indices(&self.hasher, &key, self.n_hash_functions, sbf_size);
+        let upper_bound = 2u32.pow(self.width) - 1;
+        let minimum_value = indices.iter().map(|&i| self.sbf.get(i)).min().unwrap();
+        let minimum_value = match minimum_value.checked_add(frequency) {
+            Some(v) => std::cmp::min(v, upper_bound),
+            None => upper_bound,
+        };
+        indices.iter().for_each(|&i| {
+            if self.sbf.get(i) <= minimum_value {
+                self.sbf.set(i, minimum_value);
+            }
+        });
+    }
+
+    /**
+    Get the frequency estimate for a token.
+
+    # Returns
+    * The frequency estimate, which may undershoot (or return 0 for a present token) if it has been evicted by later inserts
+
+    # Arguments
+    * key: An element of the multiset
+    */
+    pub fn get_frequency(&self, key: &str) -> u32 {
+        let key = key.to_string();
+        let indices = SpectralBloomFilter::<H>::hash_indices(
+            &self.hasher,
+            &key,
+            self.n_hash_functions,
+            self.sbf.len() as u32,
+        );
+        indices.into_iter().map(|i| self.sbf.get(i)).min().unwrap()
     }
 }
 
@@ -160,24 +309,38 @@ mod tests {
         hash_map.insert("b".to_string(), 2);
         hash_map.insert("c".to_string(), 10);
 
-        let sbf = SpectralBloomFilter::new(&hash_map, 0.01, 4);
+        let sbf = SpectralBloomFilter::new(&hash_map, 0.01, 4, MurmurHasher);
         hash_map.iter().for_each(|(token, &frequency)| {
             let frq = sbf.get_frequency(token);
             assert_eq!(frq, frequency);
         });
     }
+
+    #[test]
+    fn stable_sbf_inserts_are_readable_immediately() {
+        // With p = 0 nothing is evicted, so a freshly inserted token's frequency is exact.
+        let mut sbf: StableSpectralBloomFilter = StableSpectralBloomFilter::new(16, 0.01, 4, 0, MurmurHasher);
+        sbf.insert("a", 1);
+        sbf.insert("b", 2);
+        sbf.insert("a", 2);
+
+        assert_eq!(sbf.get_frequency("a"), 3);
+        assert_eq!(sbf.get_frequency("b"), 2);
+        assert_eq!(sbf.get_frequency("x"), 0);
+    }
+
     proptest! {
         #[test]
         fn proptest_false_negatives(counter in with_max_width(1)){
             // Even for high false positive rate (99%), and small width, there should not be any false negatives
-            let sbf = SpectralBloomFilter::new(&counter, 0.99,1);
+            let sbf = SpectralBloomFilter::new(&counter, 0.99,1, MurmurHasher);
             let false_negatives = counter.keys().filter(|token| sbf.get_frequency(token)==0).count();
             prop_assert_eq!(false_negatives,0);
         }
 
         #[test]
         fn proptest_undershoot(counter in with_max_width(10)) {
-            let sbf = SpectralBloomFilter::new(&counter, 0.99,10);
+            let sbf = SpectralBloomFilter::new(&counter, 0.99,10, MurmurHasher);
             let undershoot = counter.into_iter().filter(|(token,frequency)| sbf.get_frequency(token)<*frequency).count();
             prop_assert_eq!(undershoot,0);
         }
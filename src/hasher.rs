@@ -0,0 +1,3 @@
+//! Hash functions used by the native indexer, re-exported from the `no_std + alloc`
+//! [`crate::core::hasher`] module so the same code also serves the WASM query bundle.
+pub use crate::core::hasher::{fast, murmur3, Hasher};
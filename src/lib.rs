@@ -0,0 +1,12 @@
+extern crate alloc;
+
+pub mod compressor;
+pub mod core;
+pub mod estimator;
+pub mod hasher;
+pub mod preprocessor;
+
+// Enabled via `--features wasm` (wasm-bindgen + the `cdylib` crate-type in Cargo.toml); not part
+// of the default native build.
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,11 +1,48 @@
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use static_website_search::compressor::base2p15;
-use static_website_search::estimator::spectral_bloom_filter::SpectralBloomFilter;
+use static_website_search::estimator::binary_fuse_filter::BinaryFuseFilter;
+use static_website_search::estimator::spectral_bloom_filter::{
+    recommended_decrement, SpectralBloomFilter, StableSpectralBloomFilter,
+};
+use static_website_search::hasher::fast::FastHasher;
+use static_website_search::hasher::murmur3::MurmurHasher;
+use static_website_search::hasher::Hasher;
+use static_website_search::preprocessor::aho_corasick::{Action, AhoCorasick};
 use static_website_search::preprocessor::naive;
+use static_website_search::preprocessor::ngram;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Write};
+use std::str::FromStr;
+
+/// How per-term document frequency (`df`, used for BM25 IDF) is tallied while streaming through
+/// `tokens_file`.
+enum DocumentFrequencyTracker {
+    /// One exact count per term, the default.
+    Exact(HashMap<String, u32>),
+    /// A [`StableSpectralBloomFilter`] sized for `--expected-vocab-size`, used instead with
+    /// `--streaming-document-frequency` so memory stays bounded regardless of how many distinct
+    /// terms `tokens_file` contains, at the cost of a small, bounded undercount of `df`.
+    Streaming(StableSpectralBloomFilter<Box<dyn Hasher>>),
+}
+
+impl DocumentFrequencyTracker {
+    fn increment(&mut self, term: &str) {
+        match self {
+            DocumentFrequencyTracker::Exact(counts) => *counts.entry(term.to_string()).or_insert(0) += 1,
+            DocumentFrequencyTracker::Streaming(sbf) => sbf.insert(term, 1),
+        }
+    }
+
+    fn get(&self, term: &str) -> u32 {
+        match self {
+            DocumentFrequencyTracker::Exact(counts) => *counts.get(term).unwrap_or(&0),
+            DocumentFrequencyTracker::Streaming(sbf) => sbf.get_frequency(term),
+        }
+    }
+}
 
 #[derive(Deserialize, Debug)]
 struct Post {
@@ -22,6 +59,79 @@ struct SearchItem {
     width: u32,
     size: u32,
     n_hash_functions: u32,
+    /// Total number of (indexed) term occurrences in the document, used as `docLen` in BM25.
+    doc_len: u32,
+}
+
+/// The hash function used to derive probe indices in the streaming document-frequency filter
+/// (see `--streaming-document-frequency`). Only that filter is built server-side and never
+/// serialized into the index, so it's the one place a faster, lower-quality hasher is safe to
+/// opt into: the per-document `SpectralBloomFilter` and the vocabulary `BinaryFuseFilter` are
+/// both read back by the client (native CLI's WASM build and `static_website_search.js`, which
+/// only implement MurmurHash3), so they're always built with `MurmurHasher` regardless of this
+/// setting.
+#[derive(Debug, Clone, Copy)]
+enum HasherKind {
+    Murmur,
+    Fast,
+}
+
+impl FromStr for HasherKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "murmur" => Ok(HasherKind::Murmur),
+            "fast" => Ok(HasherKind::Fast),
+            other => Err(format!(
+                "unknown hasher '{}', expected 'murmur' or 'fast'",
+                other
+            )),
+        }
+    }
+}
+
+impl HasherKind {
+    fn build(self) -> Box<dyn Hasher> {
+        match self {
+            HasherKind::Murmur => Box::new(MurmurHasher),
+            HasherKind::Fast => Box::new(FastHasher),
+        }
+    }
+}
+
+/// Which terms get indexed, see `preprocessor::ngram::Mode`.
+#[derive(Debug, Clone, Copy)]
+enum TokenizeMode {
+    Word,
+    Ngram,
+    Combined,
+}
+
+impl FromStr for TokenizeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "word" => Ok(TokenizeMode::Word),
+            "ngram" => Ok(TokenizeMode::Ngram),
+            "combined" => Ok(TokenizeMode::Combined),
+            other => Err(format!(
+                "unknown tokenize mode '{}', expected 'word', 'ngram' or 'combined'",
+                other
+            )),
+        }
+    }
+}
+
+impl From<TokenizeMode> for ngram::Mode {
+    fn from(mode: TokenizeMode) -> Self {
+        match mode {
+            TokenizeMode::Word => ngram::Mode::Word,
+            TokenizeMode::Ngram => ngram::Mode::Ngram,
+            TokenizeMode::Combined => ngram::Mode::Combined,
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -45,6 +155,96 @@ struct Opts {
     /// Small values may affect the ranking of documents
     #[clap(short = 'w', long, default_value = "4")]
     counter_width: u32,
+
+    /// The hash function used to derive probe indices in the streaming document-frequency filter
+    /// (only used with `--streaming-document-frequency`): `murmur` (MurmurHash3, the default) or
+    /// `fast` (a cheaper multiply-xor-fold hash). The per-document and vocabulary filters shipped
+    /// to the client are always MurmurHash3, since that's the only hasher the WASM/JS query path
+    /// implements
+    #[clap(long, default_value = "murmur")]
+    hasher: HasherKind,
+
+    /// Which terms get indexed: `word` (whole words, the default), `ngram` (character k-grams
+    /// only, for prefix/typo-tolerant matching) or `combined` (both)
+    #[clap(long, default_value = "word")]
+    tokenize_mode: TokenizeMode,
+
+    /// Character k-gram length used by `ngram`/`combined` tokenize modes
+    #[clap(long, default_value = "3")]
+    ngram_size: usize,
+
+    /// File with one multi-word stopword phrase per line (e.g. "new york"), dropped from the
+    /// indexed text before single-word stopword removal
+    #[clap(long)]
+    phrase_stopwords_file: Option<String>,
+
+    /// File mapping multi-word synonyms to a canonical token, one `phrase => canonical` mapping
+    /// per line (e.g. "united states => usa")
+    #[clap(long)]
+    synonyms_file: Option<String>,
+
+    /// Brotli-compress the embedded search index (base64-encoded) instead of inlining it as raw
+    /// JSON. Shrinks the shipped `static_website_search.js` at the cost of requiring a Brotli
+    /// decoder in the page (see `decompressBrotliBase64` in the JS template)
+    #[clap(long)]
+    compress: bool,
+
+    /// Brotli quality, 0-11. Higher is smaller but slower to build. Only used with `--compress`
+    #[clap(long, default_value = "11")]
+    compression_level: u32,
+
+    /// Path to a JS file defining a global `BrotliDecode(bytes: Uint8Array): Uint8Array` (e.g.
+    /// https://github.com/foliojs/brotli.js's `decompress.js`), inlined into `demo.html` before
+    /// `static_website_search.js`. Required with `--compress`, since the generated JS calls
+    /// `BrotliDecode` to read back the compressed index.
+    #[clap(long)]
+    brotli_decoder_script: Option<String>,
+
+    /// BM25 term-frequency saturation parameter
+    #[clap(long, default_value = "1.2")]
+    bm25_k1: f32,
+
+    /// BM25 document-length normalization parameter, in range [0,1]
+    #[clap(long, default_value = "0.75")]
+    bm25_b: f32,
+
+    /// Track per-term document frequency (used for BM25 IDF) in a bounded-size
+    /// `StableSpectralBloomFilter` instead of an exact `HashMap`. Trades a small, bounded
+    /// undercount of `df` for constant memory as `tokens_file` grows, instead of holding one
+    /// entry per distinct term for the life of the build.
+    #[clap(long)]
+    streaming_document_frequency: bool,
+
+    /// Expected vocabulary size, used to size the streaming document-frequency filter. Only used
+    /// with `--streaming-document-frequency`
+    #[clap(long, default_value = "100000")]
+    expected_vocab_size: u32,
+
+    /// Number of cells evicted per insert into the streaming document-frequency filter. Defaults
+    /// to `recommended_decrement(false_positive_rate, counter_width)`. Only used with
+    /// `--streaming-document-frequency`
+    #[clap(long)]
+    streaming_df_decrement: Option<u32>,
+}
+
+/// Parses a phrase dictionary file into `(phrase, action)` entries.
+///
+/// `action_for` turns each line into its `Action`: for a stopword-phrase file, every line is
+/// dropped outright (`Action::Remove`); for a synonym file, each line is split on `=>` into the
+/// phrase and its canonical replacement (`Action::Rewrite`). `action_for` returns an error for a
+/// malformed line instead of panicking, so a bad dictionary file surfaces as a normal `main`
+/// error instead of a crash.
+fn load_phrase_dictionary(
+    path: &str,
+    action_for: impl Fn(&str) -> std::io::Result<(String, Action)>,
+) -> std::io::Result<Vec<(String, Action)>> {
+    let contents = std::fs::read_to_string(path)?;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(action_for)
+        .collect()
 }
 fn main() -> std::io::Result<()> {
     // Parse CLI options
@@ -53,17 +253,71 @@ fn main() -> std::io::Result<()> {
     let file = File::open(opts.tokens_file)?;
     let false_positive_rate = opts.false_positive_rate;
     let width = opts.counter_width;
+    let hasher_kind = opts.hasher;
+    let tokenize_mode: ngram::Mode = opts.tokenize_mode.into();
+    let ngram_size = opts.ngram_size;
+
+    // Multi-word stopword phrases and synonyms are both collapsed by the same automaton: a
+    // phrase-stopwords entry drops the phrase, a synonym entry rewrites it to its canonical token.
+    let mut dictionary: Vec<(String, Action)> = Vec::new();
+    if let Some(path) = &opts.phrase_stopwords_file {
+        dictionary.extend(load_phrase_dictionary(path, |line| {
+            Ok((line.to_lowercase(), Action::Remove))
+        })?);
+    }
+    if let Some(path) = &opts.synonyms_file {
+        dictionary.extend(load_phrase_dictionary(path, |line| {
+            let (phrase, canonical) = line.split_once("=>").ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("malformed synonym line, expected 'phrase => canonical': {}", line),
+                )
+            })?;
+            Ok((phrase.trim().to_lowercase(), Action::Rewrite(canonical.trim().to_lowercase())))
+        })?);
+    }
+    let phrase_dictionary = if dictionary.is_empty() {
+        None
+    } else {
+        Some(AhoCorasick::build(&dictionary))
+    };
 
     // Read file
     let buf_reader = BufReader::new(file);
     let tokens_json: Vec<Post> = serde_json::from_reader(buf_reader)?;
 
-    // Create search index with base2p15 encoding
+    // Create search index with base2p15 encoding, tallying BM25 ranking metadata (per-document
+    // length, and how many documents each term appears in) along the way.
+    let mut document_frequency = if opts.streaming_document_frequency {
+        let p = opts
+            .streaming_df_decrement
+            .unwrap_or_else(|| recommended_decrement(false_positive_rate, width));
+        DocumentFrequencyTracker::Streaming(StableSpectralBloomFilter::new(
+            opts.expected_vocab_size,
+            false_positive_rate,
+            width,
+            p,
+            hasher_kind.build(),
+        ))
+    } else {
+        DocumentFrequencyTracker::Exact(HashMap::new())
+    };
+    let mut vocabulary: HashSet<String> = HashSet::new();
     let search_index: Vec<SearchItem> = tokens_json
         .into_iter()
         .map(|document| {
-            let term_frequency = naive::tokenize(&document.body);
-            let sbf = SpectralBloomFilter::new(&term_frequency, false_positive_rate, width);
+            let mut term_frequency = naive::tokenize(&document.body, phrase_dictionary.as_ref());
+            ngram::fold_ngrams(&mut term_frequency, ngram_size, tokenize_mode);
+
+            let doc_len: u32 = term_frequency.values().sum();
+            term_frequency.keys().for_each(|term| {
+                document_frequency.increment(term);
+                vocabulary.insert(term.clone());
+            });
+
+            // Always MurmurHash3: this SBF is serialized into the index and read back by the
+            // WASM/JS query path, which only implements MurmurHash3 (see `HasherKind`).
+            let sbf = SpectralBloomFilter::new(&term_frequency, false_positive_rate, width, MurmurHasher);
             let encoded = base2p15::encode(&sbf.as_bit_string());
 
             SearchItem {
@@ -73,17 +327,99 @@ fn main() -> std::io::Result<()> {
                 size: sbf.sbf.len() as u32,
                 width: sbf.width,
                 n_hash_functions: sbf.n_hash_functions,
+                doc_len,
             }
         })
         .collect();
 
+    let n_documents = search_index.len() as f32;
+    let avg_doc_len = if search_index.is_empty() {
+        0.0
+    } else {
+        search_index.iter().map(|item| item.doc_len as f32).sum::<f32>() / n_documents
+    };
+
+    // A binary fuse filter over the whole vocabulary lets the client reject a query term absent
+    // from every document in one lookup, before consulting any per-document SpectralBloomFilter.
+    let vocabulary: Vec<String> = vocabulary.into_iter().collect();
+    // Always MurmurHash3, for the same reason as the per-document SBF above.
+    let vocabulary_filter = BinaryFuseFilter::build(&vocabulary, MurmurHasher);
+
+    // BM25 idf, precomputed server-side so the client only has to look up a term: idf(term) =
+    // ln(1 + (N - df + 0.5) / (df + 0.5))
+    let idf: HashMap<String, f32> = vocabulary
+        .iter()
+        .map(|term| {
+            let df = document_frequency.get(term);
+            let idf = (1.0 + (n_documents - df as f32 + 0.5) / (df as f32 + 0.5)).ln();
+            (term.clone(), idf)
+        })
+        .collect();
+
     // Write to file using template
     // Instead of template engine, use string replace as hack
     let j = serde_json::to_string(&search_index)?;
+
+    // When requested, ship the index Brotli-compressed and base64-encoded instead of as a raw JS
+    // literal; the JS template decompresses it client-side before `JSON.parse`, which requires a
+    // global `BrotliDecode` to already be loaded on the page.
+    let (search_index_literal, compressed_index_literal, brotli_decoder_script) = if opts.compress {
+        let script_path = opts.brotli_decoder_script.as_ref().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "--compress requires --brotli-decoder-script <path>: static_website_search.js's \
+                 decompressBrotliBase64 calls a global BrotliDecode(bytes) that must be loaded \
+                 before it runs (e.g. https://github.com/foliojs/brotli.js's decompress.js)",
+            )
+        })?;
+        // Escape any literal "</script" in the decoder so the HTML parser doesn't treat it as
+        // closing our wrapping <script> tag early (e.g. inside a string, regex, or comment).
+        let decoder_script = std::fs::read_to_string(script_path)?.replace("</script", "<\\/script");
+
+        let mut compressed = Vec::new();
+        {
+            let mut writer =
+                brotli::CompressorWriter::new(&mut compressed, 4096, opts.compression_level, 22);
+            writer.write_all(j.as_bytes())?;
+        }
+        (
+            "null".to_string(),
+            format!("\"{}\"", base64::encode(&compressed)),
+            format!("<script>\n{}\n</script>", decoder_script),
+        )
+    } else {
+        (j, "null".to_string(), String::new())
+    };
+
     let js_template = include_str!("assets/static_website_search.js");
-    let js_code = js_template.replace("UNIQUE_SEARCH_INDEX_PLACEHOLDER", j.as_str());
+    let js_code = js_template
+        .replace("UNIQUE_SEARCH_INDEX_PLACEHOLDER", &search_index_literal)
+        .replace("UNIQUE_COMPRESSED_INDEX_PLACEHOLDER", &compressed_index_literal)
+        .replace("UNIQUE_NGRAM_SIZE_PLACEHOLDER", &ngram_size.to_string())
+        .replace(
+            "UNIQUE_TOKENIZE_MODE_PLACEHOLDER",
+            match tokenize_mode {
+                ngram::Mode::Word => "\"word\"",
+                ngram::Mode::Ngram => "\"ngram\"",
+                ngram::Mode::Combined => "\"combined\"",
+            },
+        )
+        .replace("UNIQUE_IDF_PLACEHOLDER", &serde_json::to_string(&idf)?)
+        .replace("UNIQUE_AVG_DOC_LEN_PLACEHOLDER", &avg_doc_len.to_string())
+        .replace("UNIQUE_BM25_K1_PLACEHOLDER", &opts.bm25_k1.to_string())
+        .replace("UNIQUE_BM25_B_PLACEHOLDER", &opts.bm25_b.to_string())
+        .replace(
+            "UNIQUE_VOCAB_FILTER_PLACEHOLDER",
+            &format!("\"{}\"", base64::encode(vocabulary_filter.fingerprints())),
+        )
+        .replace(
+            "UNIQUE_VOCAB_SEGMENT_LENGTH_PLACEHOLDER",
+            &vocabulary_filter.segment_length().to_string(),
+        )
+        .replace("UNIQUE_VOCAB_SEED_PLACEHOLDER", &vocabulary_filter.seed().to_string());
     std::fs::write("static_website_search.js", js_code)?;
 
-    let demo_html = include_str!("assets/demo.html");
+    let demo_template = include_str!("assets/demo.html");
+    let demo_html = demo_template.replace("UNIQUE_BROTLI_DECODER_PLACEHOLDER", &brotli_decoder_script);
     std::fs::write("demo.html", demo_html)
 }
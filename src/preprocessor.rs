@@ -0,0 +1,5 @@
+pub mod aho_corasick;
+pub mod format_structs;
+pub mod minimize_width;
+pub mod naive;
+pub mod ngram;
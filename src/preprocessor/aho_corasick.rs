@@ -0,0 +1,194 @@
+use std::collections::{HashMap, VecDeque};
+
+/// What happens when a dictionary phrase matches during tokenization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Drop the phrase entirely, e.g. a multi-word stopword phrase like "new york".
+    Remove,
+    /// Replace the phrase with a single canonical token, e.g. a synonym mapping.
+    Rewrite(String),
+}
+
+/// An Aho-Corasick automaton over a dictionary of `(phrase, action)` pairs, used to collapse
+/// multi-word phrases and synonyms into a single index token (or drop them) in one linear pass
+/// over the document text, instead of shredding every word individually through single-word
+/// stopword removal.
+///
+/// Built from goto (trie), failure, and output links, with failure links computed by BFS so the
+/// whole text is scanned in O(n) regardless of dictionary size.
+pub struct AhoCorasick {
+    // goto[state][byte] = next state; state 0 is the root.
+    goto: Vec<HashMap<u8, usize>>,
+    fail: Vec<usize>,
+    // output[state] = the (phrase length, action) to apply if this state matches a dictionary entry.
+    output: Vec<Option<(usize, Action)>>,
+    // depth[state] = length of the path from the root to this state.
+    depth: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Build the automaton from a dictionary of lowercase phrases (bytes are matched literally,
+    /// so callers should normalize case/whitespace the same way the document text is normalized).
+    pub fn build(dictionary: &[(String, Action)]) -> Self {
+        let mut goto: Vec<HashMap<u8, usize>> = vec![HashMap::new()];
+        let mut output: Vec<Option<(usize, Action)>> = vec![None];
+        let mut depth: Vec<usize> = vec![0];
+
+        // Goto function: a trie over the dictionary phrases.
+        for (phrase, action) in dictionary {
+            let mut state = 0;
+            for &byte in phrase.as_bytes() {
+                state = match goto[state].get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        goto.push(HashMap::new());
+                        output.push(None);
+                        depth.push(depth[state] + 1);
+                        let next = goto.len() - 1;
+                        goto[state].insert(byte, next);
+                        next
+                    }
+                };
+            }
+            output[state] = Some((phrase.len(), action.clone()));
+        }
+
+        // Failure links: BFS over the trie, root's children fail to root.
+        let mut fail = vec![0usize; goto.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for &child in goto[0].values() {
+            fail[child] = 0;
+            queue.push_back(child);
+        }
+        while let Some(state) = queue.pop_front() {
+            let transitions: Vec<(u8, usize)> =
+                goto[state].iter().map(|(&byte, &next)| (byte, next)).collect();
+            for (byte, next) in transitions {
+                queue.push_back(next);
+                let mut f = fail[state];
+                while f != 0 && !goto[f].contains_key(&byte) {
+                    f = fail[f];
+                }
+                fail[next] = goto[f].get(&byte).copied().unwrap_or(0);
+                if fail[next] == next {
+                    fail[next] = 0;
+                }
+            }
+        }
+
+        AhoCorasick {
+            goto,
+            fail,
+            output,
+            depth,
+        }
+    }
+
+    /// Scan `text` in one linear pass, applying dictionary matches with leftmost-longest
+    /// resolution: when several dictionary phrases share a starting position (one is a prefix of
+    /// another, e.g. "new" / "new york"), the longest one wins; once a match is committed, later
+    /// matches that would overlap it are skipped. Synonym phrases are rewritten to their
+    /// canonical token, stopword phrases are dropped, and bytes outside any match pass through
+    /// untouched.
+    pub fn rewrite(&self, text: &str) -> String {
+        let bytes = text.as_bytes();
+        let mut result: Vec<u8> = Vec::with_capacity(bytes.len());
+        let mut state = 0usize;
+        let mut cursor = 0usize;
+        // The best (start, length, action) match found so far that shares its start with the
+        // live trie path `state` currently represents.
+        let mut pending: Option<(usize, usize, Action)> = None;
+
+        for (i, &byte) in bytes.iter().enumerate() {
+            while state != 0 && !self.goto[state].contains_key(&byte) {
+                state = self.fail[state];
+            }
+            state = self.goto[state].get(&byte).copied().unwrap_or(0);
+
+            // The live trie path ending at `i` always has length `depth[state]`, regardless of
+            // whether we arrived here via direct transitions or a failure fallback, so its start
+            // is always `i + 1 - depth[state]`.
+            let current_start = i + 1 - self.depth[state];
+
+            if let Some((pending_start, length, action)) = pending.take() {
+                if pending_start == current_start {
+                    // Still the same run: keep the longer match as pending.
+                    pending = Some((pending_start, length, action));
+                } else {
+                    // The run that produced `pending` can't extend any further; commit it.
+                    Self::commit(pending_start, length, &action, bytes, &mut cursor, &mut result);
+                }
+            }
+
+            if let Some((length, action)) = &self.output[state] {
+                pending = Some((current_start, *length, action.clone()));
+            }
+        }
+
+        if let Some((start, length, action)) = pending {
+            Self::commit(start, length, &action, bytes, &mut cursor, &mut result);
+        }
+        result.extend_from_slice(&bytes[cursor..]);
+        String::from_utf8(result).unwrap_or_default()
+    }
+
+    fn commit(
+        start: usize,
+        length: usize,
+        action: &Action,
+        bytes: &[u8],
+        cursor: &mut usize,
+        result: &mut Vec<u8>,
+    ) {
+        if start < *cursor {
+            // Overlaps a match already committed; skip it.
+            return;
+        }
+        result.extend_from_slice(&bytes[*cursor..start]);
+        match action {
+            Action::Remove => {}
+            Action::Rewrite(canonical) => {
+                if matches!(result.last(), Some(&b) if b != b' ') {
+                    result.push(b' ');
+                }
+                result.extend_from_slice(canonical.as_bytes());
+            }
+        }
+        *cursor = start + length;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_multi_word_stopword_phrase() {
+        let ac = AhoCorasick::build(&[("new york".to_string(), Action::Remove)]);
+        assert_eq!(ac.rewrite("i love new york city"), "i love  city");
+    }
+
+    #[test]
+    fn rewrites_synonym_to_canonical_token() {
+        let ac = AhoCorasick::build(&[(
+            "united states".to_string(),
+            Action::Rewrite("usa".to_string()),
+        )]);
+        assert_eq!(ac.rewrite("born in the united states today"), "born in the usa today");
+    }
+
+    #[test]
+    fn leftmost_longest_prefers_the_longer_overlapping_match() {
+        let ac = AhoCorasick::build(&[
+            ("new".to_string(), Action::Rewrite("N".to_string())),
+            ("new york".to_string(), Action::Rewrite("NY".to_string())),
+        ]);
+        assert_eq!(ac.rewrite("new york"), "NY");
+    }
+
+    #[test]
+    fn text_without_matches_is_unchanged() {
+        let ac = AhoCorasick::build(&[("new york".to_string(), Action::Remove)]);
+        assert_eq!(ac.rewrite("hello world"), "hello world");
+    }
+}
@@ -1,19 +1,32 @@
+use crate::preprocessor::aho_corasick::AhoCorasick;
 use std::collections::{HashMap, HashSet};
 /// Does the following
 /// - Remove non alphabetic characters
 /// - Split on whitespace
 /// - Convert to lowercase
-/// - Remove stopwords, listed in stopwords.txt
+/// - Collapse multi-word stopword phrases / synonyms via `phrase_dictionary`, if given
+/// - Remove single-word stopwords, listed in stopwords.txt
 /// - Create a counter, with words as key and their frequencies as value
-pub fn tokenize(text: &String) -> HashMap<String, u32> {
+pub fn tokenize(text: &str, phrase_dictionary: Option<&AhoCorasick>) -> HashMap<String, u32> {
     let stopwords = include_str!("../assets/stopwords.txt");
     let stopwords: HashSet<String> = stopwords.split_whitespace().map(String::from).collect();
 
+    let normalized: String = text
+        .replace(|c: char| !c.is_alphabetic(), " ")
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let normalized = match phrase_dictionary {
+        Some(dictionary) => dictionary.rewrite(&normalized),
+        None => normalized,
+    };
+
     let mut counter: HashMap<String, u32> = HashMap::new();
-    text.replace(|c: char| !c.is_alphabetic(), " ")
+    normalized
         .split_whitespace()
-        .map(str::to_lowercase)
-        .filter(|word| !stopwords.contains(word))
-        .for_each(|word| *counter.entry(word).or_insert(0) += 1);
+        .filter(|word| !stopwords.contains(*word))
+        .for_each(|word| *counter.entry(word.to_string()).or_insert(0) += 1);
     counter
-}
\ No newline at end of file
+}
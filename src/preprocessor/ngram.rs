@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+/// Which terms get indexed: whole words only (today's behaviour), character k-grams only, or
+/// both folded into the same counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Word,
+    Ngram,
+    Combined,
+}
+
+/// Overlapping character k-grams of `word`, e.g. `k=3`, `"search"` -> `"sea","ear","arc","rch"`.
+///
+/// Words shorter than `k` produce no k-grams (there aren't enough characters to probe a
+/// sub-string match on).
+pub fn char_ngrams(word: &str, k: usize) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < k {
+        return Vec::new();
+    }
+    (0..=chars.len() - k)
+        .map(|i| chars[i..i + k].iter().collect())
+        .collect()
+}
+
+/// Given the word-level counter produced by `preprocessor::naive::tokenize`, fold in character
+/// k-grams per `mode`: a k-gram's count is the sum of the counts of every word it appears in, so
+/// common substrings accumulate the same way whole words do.
+///
+/// This gives approximate (prefix / substring / single-typo) matching at query time: a query
+/// term is decomposed into the same k-grams and scored by how many are present in a document's
+/// filter.
+pub fn fold_ngrams(counter: &mut HashMap<String, u32>, k: usize, mode: Mode) {
+    if mode == Mode::Word {
+        return;
+    }
+
+    let words: Vec<(String, u32)> = counter.drain().collect();
+    let mut ngram_counter: HashMap<String, u32> = HashMap::new();
+    for (word, frequency) in &words {
+        for ngram in char_ngrams(word, k) {
+            *ngram_counter.entry(ngram).or_insert(0) += frequency;
+        }
+    }
+
+    match mode {
+        Mode::Word => unreachable!(),
+        Mode::Ngram => *counter = ngram_counter,
+        Mode::Combined => {
+            *counter = words.into_iter().collect();
+            ngram_counter.into_iter().for_each(|(ngram, frequency)| {
+                *counter.entry(ngram).or_insert(0) += frequency;
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn char_ngrams_of_search() {
+        assert_eq!(
+            char_ngrams("search", 3),
+            vec!["sea", "ear", "arc", "rch"]
+        );
+    }
+
+    #[test]
+    fn char_ngrams_shorter_than_k() {
+        assert!(char_ngrams("ab", 3).is_empty());
+    }
+
+    #[test]
+    fn fold_ngrams_word_mode_is_noop() {
+        let mut counter = HashMap::new();
+        counter.insert("search".to_string(), 2);
+        fold_ngrams(&mut counter, 3, Mode::Word);
+        assert_eq!(counter.get("search"), Some(&2));
+        assert_eq!(counter.get("sea"), None);
+    }
+
+    #[test]
+    fn fold_ngrams_ngram_mode_replaces_words() {
+        let mut counter = HashMap::new();
+        counter.insert("search".to_string(), 2);
+        fold_ngrams(&mut counter, 3, Mode::Ngram);
+        assert_eq!(counter.get("search"), None);
+        assert_eq!(counter.get("sea"), Some(&2));
+        assert_eq!(counter.get("rch"), Some(&2));
+    }
+
+    #[test]
+    fn fold_ngrams_combined_mode_keeps_both() {
+        let mut counter = HashMap::new();
+        counter.insert("search".to_string(), 2);
+        fold_ngrams(&mut counter, 3, Mode::Combined);
+        assert_eq!(counter.get("search"), Some(&2));
+        assert_eq!(counter.get("sea"), Some(&2));
+    }
+}
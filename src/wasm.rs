@@ -0,0 +1,102 @@
+//! WASM query bundle, compiled via `wasm-bindgen` from the same [`crate::core`] logic the
+//! native indexer relies on, so the browser can never drift from the Rust implementation the
+//! way the hand-maintained `static_website_search.js` could.
+//!
+//! Built with `wasm-pack build --features wasm`. The generated HTML loads the resulting `.wasm`,
+//! constructs one `SearchIndex` from the embedded index JSON plus the BM25/vocabulary-filter
+//! metadata `main.rs` emitted alongside it, and calls `.query(term)` per keystroke.
+use crate::core::hasher::murmur3::MurmurHasher;
+use crate::core::query::{self, Bm25Params, IndexEntry, NgramMode, VocabularyFilter};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct SearchIndex {
+    entries: Vec<IndexEntry>,
+    ngram_size: usize,
+    tokenize_mode: NgramMode,
+    idf: BTreeMap<String, f32>,
+    avg_doc_len: f32,
+    bm25_k1: f32,
+    bm25_b: f32,
+    vocab_fingerprints: Vec<u8>,
+    vocab_segment_length: u32,
+    vocab_seed: u32,
+}
+
+#[wasm_bindgen]
+impl SearchIndex {
+    /// Parse the `UNIQUE_SEARCH_INDEX_PLACEHOLDER` JSON payload and the BM25/vocabulary-filter
+    /// metadata (`UNIQUE_IDF_PLACEHOLDER`, `UNIQUE_AVG_DOC_LEN_PLACEHOLDER`,
+    /// `UNIQUE_BM25_K1_PLACEHOLDER`, `UNIQUE_BM25_B_PLACEHOLDER`,
+    /// `UNIQUE_VOCAB_FILTER_PLACEHOLDER`/`..._SEGMENT_LENGTH_.../..._SEED_...`) emitted by
+    /// `main.rs`.
+    ///
+    /// `tokenize_mode` must match `--tokenize-mode` at index build time: `"word"`, `"ngram"` or
+    /// `"combined"`.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        index_json: &str,
+        ngram_size: usize,
+        tokenize_mode: &str,
+        idf_json: &str,
+        avg_doc_len: f32,
+        bm25_k1: f32,
+        bm25_b: f32,
+        vocab_fingerprints: Vec<u8>,
+        vocab_segment_length: u32,
+        vocab_seed: u32,
+    ) -> Result<SearchIndex, JsValue> {
+        let entries: Vec<IndexEntry> =
+            serde_json::from_str(index_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let idf: BTreeMap<String, f32> =
+            serde_json::from_str(idf_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let tokenize_mode = match tokenize_mode {
+            "word" => NgramMode::Word,
+            "ngram" => NgramMode::Ngram,
+            "combined" => NgramMode::Combined,
+            other => return Err(JsValue::from_str(&alloc::format!("unknown tokenize mode '{}'", other))),
+        };
+        Ok(SearchIndex {
+            entries,
+            ngram_size,
+            tokenize_mode,
+            idf,
+            avg_doc_len,
+            bm25_k1,
+            bm25_b,
+            vocab_fingerprints,
+            vocab_segment_length,
+            vocab_seed,
+        })
+    }
+
+    /// Score every document against `term` with BM25, returning `[url, title, score]` triples
+    /// sorted by descending score. Query tokens absent from the vocabulary filter are dropped
+    /// before scoring, mirroring `static_website_search.js::search`.
+    pub fn query(&self, term: &str) -> JsValue {
+        let bm25 = Bm25Params {
+            idf: &self.idf,
+            avg_doc_len: self.avg_doc_len,
+            k1: self.bm25_k1,
+            b: self.bm25_b,
+        };
+        let vocab_filter = VocabularyFilter {
+            fingerprints: &self.vocab_fingerprints,
+            segment_length: self.vocab_segment_length,
+            seed: self.vocab_seed,
+        };
+        let results = query::search(
+            &self.entries,
+            term,
+            self.ngram_size,
+            self.tokenize_mode,
+            &MurmurHasher,
+            &bm25,
+            &vocab_filter,
+        );
+        JsValue::from_serde(&results).unwrap_or(JsValue::NULL)
+    }
+}